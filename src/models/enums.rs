@@ -0,0 +1,28 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum)]
+pub enum ReceiptType {
+    Action,
+    Data,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum)]
+pub enum ActionType {
+    CreateAccount,
+    DeployContract,
+    FunctionCall,
+    Transfer,
+    Stake,
+    AddKey,
+    DeleteKey,
+    DeleteAccount,
+    Delegate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum)]
+pub enum ExecutionOutcomeStatus {
+    Unknown,
+    Failure,
+    SuccessValue,
+    SuccessReceiptId,
+}
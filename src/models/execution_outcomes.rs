@@ -0,0 +1,79 @@
+use bigdecimal::BigDecimal;
+use num_traits::cast::FromPrimitive;
+use serde_json::{json, Value};
+
+use near_indexer::near_primitives::views::ExecutionStatusView;
+
+use crate::models::enums::ExecutionOutcomeStatus;
+use crate::schema;
+use schema::{execution_outcome_receipts, execution_outcomes};
+
+#[derive(Insertable, Clone)]
+pub struct ExecutionOutcome {
+    pub receipt_id: Vec<u8>,
+    pub block_height: BigDecimal,
+    pub gas_burnt: BigDecimal,
+    pub tokens_burnt: BigDecimal,
+    pub logs: Value,
+    pub status: ExecutionOutcomeStatus,
+    pub status_value: Option<Value>,
+}
+
+impl ExecutionOutcome {
+    pub fn from_execution_outcome_view(
+        outcome: &near_indexer::near_primitives::views::ExecutionOutcomeWithIdView,
+        block_height: u64,
+    ) -> Self {
+        let (status, status_value) = match &outcome.outcome.status {
+            ExecutionStatusView::Unknown => (ExecutionOutcomeStatus::Unknown, None),
+            ExecutionStatusView::SuccessValue(value) => (
+                ExecutionOutcomeStatus::SuccessValue,
+                Some(json!({ "value": base64::encode(value) })),
+            ),
+            ExecutionStatusView::SuccessReceiptId(receipt_id) => (
+                ExecutionOutcomeStatus::SuccessReceiptId,
+                Some(json!({ "receipt_id": receipt_id })),
+            ),
+            ExecutionStatusView::Failure(tx_execution_error) => (
+                ExecutionOutcomeStatus::Failure,
+                Some(json!({ "error": tx_execution_error })),
+            ),
+        };
+
+        Self {
+            receipt_id: outcome.id.as_ref().to_vec(),
+            block_height: block_height.into(),
+            gas_burnt: BigDecimal::from_u64(outcome.outcome.gas_burnt).unwrap_or_else(|| 0.into()),
+            tokens_burnt: BigDecimal::from_u128(outcome.outcome.tokens_burnt)
+                .unwrap_or_else(|| 0.into()),
+            logs: json!(outcome.outcome.logs),
+            status,
+            status_value,
+        }
+    }
+}
+
+#[derive(Insertable, Clone)]
+pub struct ExecutionOutcomeReceipt {
+    pub execution_outcome_receipt_id: Vec<u8>,
+    pub index: i32,
+    pub receipt_id: Vec<u8>,
+}
+
+impl ExecutionOutcomeReceipt {
+    pub fn from_outcome_view(
+        outcome: &near_indexer::near_primitives::views::ExecutionOutcomeWithIdView,
+    ) -> Vec<Self> {
+        outcome
+            .outcome
+            .receipt_ids
+            .iter()
+            .enumerate()
+            .map(|(index, receipt_id)| Self {
+                execution_outcome_receipt_id: outcome.id.as_ref().to_vec(),
+                index: index as i32,
+                receipt_id: receipt_id.as_ref().to_vec(),
+            })
+            .collect()
+    }
+}
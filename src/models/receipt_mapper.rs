@@ -0,0 +1,71 @@
+use sha2::{Digest, Sha256};
+
+/// Rewrites account ids and public keys embedded in an indexed receipt. Used when
+/// ingesting a forked/test-network state where every reference has to point at a
+/// remapped account, otherwise replayed receipts point at mainnet accounts that no
+/// longer exist on the fork.
+pub trait ReceiptMapper {
+    fn map_account_id(&self, account_id: &str) -> String;
+    fn map_public_key(&self, public_key: &str) -> String;
+}
+
+/// Leaves every reference untouched. This is the implicit default: regular
+/// (non-forked) indexing passes `None` rather than constructing this.
+pub struct IdentityReceiptMapper;
+
+impl ReceiptMapper for IdentityReceiptMapper {
+    fn map_account_id(&self, account_id: &str) -> String {
+        account_id.to_string()
+    }
+
+    fn map_public_key(&self, public_key: &str) -> String {
+        public_key.to_string()
+    }
+}
+
+/// Deterministically rewrites account ids and public keys by hashing them together
+/// with a configured secret, so the same source value always maps to the same
+/// replacement without the operator needing to maintain an explicit lookup table.
+pub struct HashedAccountMapper {
+    secret: String,
+}
+
+impl HashedAccountMapper {
+    pub fn new(secret: String) -> Self {
+        Self { secret }
+    }
+
+    fn hash(&self, value: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.secret.as_bytes());
+        hasher.update(value.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+impl ReceiptMapper for HashedAccountMapper {
+    fn map_account_id(&self, account_id: &str) -> String {
+        format!("{}.near", &hex::encode(self.hash(account_id))[..40])
+    }
+
+    fn map_public_key(&self, public_key: &str) -> String {
+        // NEAR public keys are base58, not hex — a hex digest can contain '0',
+        // which base58's alphabet excludes, so hex-encoding would yield a
+        // syntactically invalid key. A SHA-256 digest is conveniently the same 32
+        // bytes as an ed25519 public key, so the whole hash can be base58-encoded
+        // directly.
+        format!("ed25519:{}", bs58::encode(self.hash(public_key)).into_string())
+    }
+}
+
+pub(crate) fn map_account_id(mapper: Option<&dyn ReceiptMapper>, account_id: &str) -> String {
+    mapper
+        .map(|mapper| mapper.map_account_id(account_id))
+        .unwrap_or_else(|| account_id.to_string())
+}
+
+pub(crate) fn map_public_key(mapper: Option<&dyn ReceiptMapper>, public_key: &str) -> String {
+    mapper
+        .map(|mapper| mapper.map_public_key(public_key))
+        .unwrap_or_else(|| public_key.to_string())
+}
@@ -7,12 +7,44 @@ use serde_json::{json, Value};
 use near_indexer::near_primitives::views::{ActionView, DataReceiverView};
 
 use crate::models::enums::{ActionType, ReceiptType};
+use crate::models::receipt_mapper::{map_account_id, map_public_key, ReceiptMapper};
 use crate::schema;
 use schema::{
     receipt_action_actions, receipt_action_input_data, receipt_action_output_data, receipt_actions,
     receipt_data, receipts,
 };
 
+/// Default cap for `decode_function_call_args`, used when a deployment doesn't
+/// configure its own. Callers (e.g. indexer config) may pass a different limit.
+pub const DEFAULT_MAX_FUNCTION_CALL_ARGS_JSON_DECODE_BYTES: usize = 4096;
+
+/// Best-effort decode of `FunctionCall` args: embed the parsed JSON under
+/// `args_json` (alongside the raw base64 form under `args_base64`) when the bytes
+/// are valid UTF-8 JSON and within `max_decode_bytes`. Payloads over the cap are
+/// not even base64-encoded into the column — only their length is recorded — so a
+/// multi-megabyte arg can't blow up the `args` column either way.
+fn decode_function_call_args(args: &[u8], max_decode_bytes: usize) -> Value {
+    if args.len() > max_decode_bytes {
+        return json!({
+            "args_is_binary": true,
+            "args_len": args.len(),
+        });
+    }
+
+    let args_base64 = base64::encode(args);
+
+    match std::str::from_utf8(args).ok().and_then(|s| serde_json::from_str::<Value>(s).ok()) {
+        Some(args_json) => json!({
+            "args_base64": args_base64,
+            "args_json": args_json,
+        }),
+        None => json!({
+            "args_base64": args_base64,
+            "args_is_binary": true,
+        }),
+    }
+}
+
 #[derive(Insertable, Clone)]
 pub struct Receipt {
     pub receipt_id: Vec<u8>,
@@ -26,12 +58,13 @@ impl Receipt {
     pub fn from_receipt_view(
         receipt: &near_indexer::near_primitives::views::ReceiptView,
         block_height: u64,
+        mapper: Option<&dyn ReceiptMapper>,
     ) -> Self {
         Self {
             receipt_id: receipt.receipt_id.as_ref().to_vec(),
             block_height: block_height.into(),
-            predecessor_id: receipt.predecessor_id.to_string(),
-            receiver_id: receipt.receiver_id.to_string(),
+            predecessor_id: map_account_id(mapper, &receipt.predecessor_id.to_string()),
+            receiver_id: map_account_id(mapper, &receipt.receiver_id.to_string()),
             receipt_kind: match receipt.receipt {
                 near_indexer::near_primitives::views::ReceiptEnumView::Action { .. } => {
                     ReceiptType::Action
@@ -78,6 +111,9 @@ pub struct ReceiptAction {
     pub signer_id: String,
     pub signer_public_key: String,
     pub gas_price: BigDecimal,
+    // NEP-366: the account that paid gas on behalf of `signer_id`, if this receipt
+    // was produced from a meta-transaction.
+    pub relayer_id: Option<String>,
 }
 
 impl TryFrom<&near_indexer::near_primitives::views::ReceiptView> for ReceiptAction {
@@ -86,18 +122,35 @@ impl TryFrom<&near_indexer::near_primitives::views::ReceiptView> for ReceiptActi
     fn try_from(
         receipt_view: &near_indexer::near_primitives::views::ReceiptView,
     ) -> Result<Self, Self::Error> {
+        Self::try_from_view(receipt_view, None)
+    }
+}
+
+impl ReceiptAction {
+    /// Same as the `TryFrom<&ReceiptView>` impl, but additionally rewrites
+    /// `signer_id`, `signer_public_key`, and `relayer_id` through `mapper` when one
+    /// is supplied (forked/test-network ingestion). Pass `None` for regular
+    /// mainnet indexing.
+    pub fn try_from_view(
+        receipt_view: &near_indexer::near_primitives::views::ReceiptView,
+        mapper: Option<&dyn ReceiptMapper>,
+    ) -> Result<Self, &'static str> {
         if let near_indexer::near_primitives::views::ReceiptEnumView::Action {
             signer_id,
             signer_public_key,
             gas_price,
+            relayer_id,
             ..
         } = &receipt_view.receipt {
             Ok(
                 Self {
                     receipt_id: receipt_view.receipt_id.as_ref().to_vec(),
-                    signer_id: signer_id.to_string(),
-                    signer_public_key: signer_public_key.to_string(),
+                    signer_id: map_account_id(mapper, &signer_id.to_string()),
+                    signer_public_key: map_public_key(mapper, &signer_public_key.to_string()),
                     gas_price: BigDecimal::from_u128(*gas_price).unwrap_or_else(||0.into()),
+                    relayer_id: relayer_id
+                        .as_ref()
+                        .map(|account_id| map_account_id(mapper, &account_id.to_string())),
                 }
             )
         } else {
@@ -106,6 +159,13 @@ impl TryFrom<&near_indexer::near_primitives::views::ReceiptView> for ReceiptActi
     }
 }
 
+/// Sentinel `parent_index` for a top-level action (one that wasn't unwrapped out of
+/// a `Delegate` envelope). `parent_index` is part of the table's primary key
+/// alongside `receipt_id`/`index`, so it can't be nullable — `index` alone is only
+/// unique *within* a parent, and the outer `Delegate` row and its first unwrapped
+/// child both start counting from 0.
+const NO_PARENT_INDEX: i32 = -1;
+
 #[derive(Insertable, Clone)]
 #[table_name = "receipt_action_actions"]
 pub struct ReceiptActionAction {
@@ -113,14 +173,79 @@ pub struct ReceiptActionAction {
     pub index: i32,
     pub action_kind: ActionType,
     pub args: serde_json::Value,
+    // `NO_PARENT_INDEX` for top-level actions; otherwise the `index` of the outer
+    // NEP-366 `Delegate` row that carried this action.
+    pub parent_index: i32,
 }
 
 impl ReceiptActionAction {
+    /// Converts a single `ActionView` into one or more rows. Most actions produce a
+    /// single row, but a NEP-366 `Delegate` envelope also recursively flattens the
+    /// actions it wraps, tagging each with `parent_index` set to the envelope's own
+    /// `index` so both the relayer-submitted envelope and the user's real intent are
+    /// queryable.
     pub fn from_action_view(
         receipt_id: Vec<u8>,
         index: i32,
         action_view: &near_indexer::near_primitives::views::ActionView,
-    ) -> Self {
+        max_function_call_args_json_decode_bytes: usize,
+        mapper: Option<&dyn ReceiptMapper>,
+    ) -> Vec<Self> {
+        Self::from_action_view_with_parent(
+            receipt_id,
+            index,
+            action_view,
+            NO_PARENT_INDEX,
+            max_function_call_args_json_decode_bytes,
+            mapper,
+        )
+    }
+
+    fn from_action_view_with_parent(
+        receipt_id: Vec<u8>,
+        index: i32,
+        action_view: &near_indexer::near_primitives::views::ActionView,
+        parent_index: i32,
+        max_function_call_args_json_decode_bytes: usize,
+        mapper: Option<&dyn ReceiptMapper>,
+    ) -> Vec<Self> {
+        if let ActionView::Delegate {
+            delegate_action,
+            signature,
+        } = &action_view
+        {
+            let mut rows = vec![Self {
+                receipt_id: receipt_id.clone(),
+                index,
+                action_kind: ActionType::Delegate,
+                args: json!({
+                    "sender_id": map_account_id(mapper, &delegate_action.sender_id.to_string()),
+                    "receiver_id": map_account_id(mapper, &delegate_action.receiver_id.to_string()),
+                    "nonce": delegate_action.nonce,
+                    "max_block_height": delegate_action.max_block_height,
+                    "signature": signature.to_string(),
+                }),
+                parent_index,
+            }];
+
+            for (child_index, non_delegate_action) in delegate_action.actions.iter().enumerate() {
+                let inner_action_view: ActionView = near_indexer::near_primitives::transaction::Action::from(
+                    non_delegate_action.clone(),
+                )
+                .into();
+                rows.extend(Self::from_action_view_with_parent(
+                    receipt_id.clone(),
+                    child_index as i32,
+                    &inner_action_view,
+                    index,
+                    max_function_call_args_json_decode_bytes,
+                    mapper,
+                ));
+            }
+
+            return rows;
+        }
+
         let (action_kind, args): (ActionType, Value) = match &action_view {
             ActionView::CreateAccount => (ActionType::CreateAccount, json!({})),
             ActionView::DeployContract { code } => {
@@ -135,7 +260,7 @@ impl ReceiptActionAction {
                 ActionType::FunctionCall,
                 json!({
                     "method_name": method_name,
-                    "args": args,
+                    "args": decode_function_call_args(args, max_function_call_args_json_decode_bytes),
                     "gas": gas,
                     "deposit": deposit.to_string(),
                 }),
@@ -148,7 +273,7 @@ impl ReceiptActionAction {
                 ActionType::Stake,
                 json!({
                     "stake": stake.to_string(),
-                    "public_key": public_key,
+                    "public_key": map_public_key(mapper, &public_key.to_string()),
                 }),
             ),
             ActionView::AddKey {
@@ -157,29 +282,31 @@ impl ReceiptActionAction {
             } => (
                 ActionType::AddKey,
                 json!({
-                    "public_key": public_key,
+                    "public_key": map_public_key(mapper, &public_key.to_string()),
                     "access_key": access_key,
                 }),
             ),
             ActionView::DeleteKey { public_key } => (
                 ActionType::DeleteKey,
                 json!({
-                    "public_key": public_key,
+                    "public_key": map_public_key(mapper, &public_key.to_string()),
                 }),
             ),
             ActionView::DeleteAccount { beneficiary_id } => (
                 ActionType::DeleteAccount,
                 json!({
-                    "beneficiary_id": beneficiary_id,
+                    "beneficiary_id": map_account_id(mapper, &beneficiary_id.to_string()),
                 }),
             ),
+            ActionView::Delegate { .. } => unreachable!("handled above"),
         };
-        Self {
+        vec![Self {
             receipt_id,
             index,
             args,
             action_kind,
-        }
+            parent_index,
+        }]
     }
 }
 
@@ -208,11 +335,104 @@ pub struct ReceiptActionOutputData {
 }
 
 impl ReceiptActionOutputData {
-    pub fn from_data_receiver(receipt_id: Vec<u8>, data_receiver: &DataReceiverView) -> Self {
+    pub fn from_data_receiver(
+        receipt_id: Vec<u8>,
+        data_receiver: &DataReceiverView,
+        mapper: Option<&dyn ReceiptMapper>,
+    ) -> Self {
         Self {
             receipt_id,
             data_id: data_receiver.data_id.as_ref().to_vec(),
-            receiver_id: data_receiver.receiver_id.to_string(),
+            receiver_id: map_account_id(mapper, &data_receiver.receiver_id.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_indexer::near_primitives::account::id::AccountId;
+    use near_indexer::near_primitives::action::delegate::DelegateAction;
+    use near_indexer::near_primitives::transaction::{Action, CreateAccountAction, TransferAction};
+    use near_crypto::{KeyType, PublicKey, Signature};
+    use std::convert::TryInto;
+
+    fn delegate_action_view_with_two_inner_actions() -> ActionView {
+        let inner_actions = vec![
+            Action::CreateAccount(CreateAccountAction {}),
+            Action::Transfer(TransferAction { deposit: 1 }),
+        ];
+
+        let delegate_action = DelegateAction {
+            sender_id: "alice.near".parse::<AccountId>().unwrap(),
+            receiver_id: "bob.near".parse::<AccountId>().unwrap(),
+            actions: inner_actions
+                .into_iter()
+                .map(|action| action.try_into().unwrap())
+                .collect(),
+            nonce: 1,
+            max_block_height: 100,
+            public_key: PublicKey::empty(KeyType::ED25519),
+        };
+
+        ActionView::Delegate {
+            delegate_action,
+            signature: Signature::empty(KeyType::ED25519),
         }
     }
+
+    #[test]
+    fn flattens_delegate_action_with_non_colliding_indexes() {
+        let rows = ReceiptActionAction::from_action_view(
+            vec![1, 2, 3],
+            5,
+            &delegate_action_view_with_two_inner_actions(),
+            DEFAULT_MAX_FUNCTION_CALL_ARGS_JSON_DECODE_BYTES,
+            None,
+        );
+
+        assert_eq!(rows.len(), 3);
+
+        assert_eq!(rows[0].index, 5);
+        assert_eq!(rows[0].parent_index, NO_PARENT_INDEX);
+        assert_eq!(rows[0].action_kind, ActionType::Delegate);
+
+        assert_eq!(rows[1].index, 0);
+        assert_eq!(rows[1].parent_index, 5);
+        assert_eq!(rows[1].action_kind, ActionType::CreateAccount);
+
+        assert_eq!(rows[2].index, 1);
+        assert_eq!(rows[2].parent_index, 5);
+        assert_eq!(rows[2].action_kind, ActionType::Transfer);
+    }
+
+    #[test]
+    fn decode_function_call_args_at_cap_is_decoded_as_json() {
+        let args = br#"{"a":1}"#;
+        let decoded = decode_function_call_args(args, args.len());
+
+        assert_eq!(decoded["args_json"], json!({ "a": 1 }));
+        assert!(decoded.get("args_is_binary").is_none());
+    }
+
+    #[test]
+    fn decode_function_call_args_over_cap_is_not_embedded() {
+        let args = br#"{"a":1}"#;
+        let decoded = decode_function_call_args(args, args.len() - 1);
+
+        assert_eq!(decoded["args_is_binary"], json!(true));
+        assert_eq!(decoded["args_len"], json!(args.len()));
+        assert!(decoded.get("args_base64").is_none());
+        assert!(decoded.get("args_json").is_none());
+    }
+
+    #[test]
+    fn decode_function_call_args_non_utf8_under_cap_is_flagged_binary() {
+        let args = [0xFFu8, 0xFE];
+        let decoded = decode_function_call_args(&args, 10);
+
+        assert_eq!(decoded["args_is_binary"], json!(true));
+        assert_eq!(decoded["args_base64"], json!(base64::encode(args)));
+        assert!(decoded.get("args_json").is_none());
+    }
 }
@@ -0,0 +1,102 @@
+table! {
+    use diesel::sql_types::*;
+    use crate::models::enums::ReceiptTypeMapping;
+
+    receipts (receipt_id) {
+        receipt_id -> Bytea,
+        block_height -> Numeric,
+        predecessor_id -> Text,
+        receiver_id -> Text,
+        receipt_kind -> ReceiptTypeMapping,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+
+    receipt_data (data_id) {
+        data_id -> Bytea,
+        receipt_id -> Bytea,
+        data -> Nullable<Bytea>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+
+    receipt_actions (receipt_id) {
+        receipt_id -> Bytea,
+        signer_id -> Text,
+        signer_public_key -> Text,
+        gas_price -> Numeric,
+        relayer_id -> Nullable<Text>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::models::enums::ActionTypeMapping;
+
+    receipt_action_actions (receipt_id, index, parent_index) {
+        receipt_id -> Bytea,
+        index -> Integer,
+        action_kind -> ActionTypeMapping,
+        args -> Jsonb,
+        parent_index -> Integer,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+
+    receipt_action_input_data (receipt_id, data_id) {
+        receipt_id -> Bytea,
+        data_id -> Bytea,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+
+    receipt_action_output_data (receipt_id, data_id) {
+        receipt_id -> Bytea,
+        data_id -> Bytea,
+        receiver_id -> Text,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::models::enums::ExecutionOutcomeStatusMapping;
+
+    execution_outcomes (receipt_id) {
+        receipt_id -> Bytea,
+        block_height -> Numeric,
+        gas_burnt -> Numeric,
+        tokens_burnt -> Numeric,
+        logs -> Jsonb,
+        status -> ExecutionOutcomeStatusMapping,
+        status_value -> Nullable<Jsonb>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+
+    execution_outcome_receipts (execution_outcome_receipt_id, index) {
+        execution_outcome_receipt_id -> Bytea,
+        index -> Integer,
+        receipt_id -> Bytea,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(
+    receipts,
+    receipt_data,
+    receipt_actions,
+    receipt_action_actions,
+    receipt_action_input_data,
+    receipt_action_output_data,
+    execution_outcomes,
+    execution_outcome_receipts,
+);